@@ -0,0 +1,9 @@
+#![no_main]
+
+use bytes_inverse::{map, unmap};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let mapped = map(data);
+    assert_eq!(unmap(&mapped).unwrap(), data);
+});
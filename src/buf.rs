@@ -0,0 +1,147 @@
+use crate::core::{check_group_size, Error};
+use bytes::{Buf, BufMut};
+
+/// Maps `bytes` directly into a [`bytes::BufMut`], avoiding the intermediate `Vec` that
+/// [`crate::core::map`] allocates.
+///
+/// This lets callers assembling a larger buffer (e.g. a multi-field ordered key in a
+/// [`bytes::BytesMut`]) append the mapped bytes in place instead of allocating a `Vec` just to
+/// copy it back out.
+///
+/// # Type Parameters
+/// * `N` - The group size, must be between 1 and 255
+pub fn map_buf<const N: usize, B: BufMut>(bytes: &[u8], dst: &mut B) {
+    check_group_size::<N>();
+
+    for (idx, val) in bytes.iter().enumerate() {
+        if idx != 0 && idx.is_multiple_of(N) {
+            dst.put_u8(0);
+        }
+        dst.put_u8(!val);
+    }
+
+    let rem = bytes.len() % N;
+    let padding = if bytes.is_empty() {
+        N
+    } else if rem == 0 {
+        0
+    } else {
+        N - rem
+    };
+    for _ in 0..padding {
+        dst.put_u8(!0);
+    }
+    dst.put_u8((padding + 1) as u8);
+}
+
+/// Unmaps a previously mapped [`bytes::Buf`] directly into a [`bytes::BufMut`], avoiding the
+/// intermediate `Vec` that [`crate::core::unmap`] allocates.
+///
+/// # Type Parameters
+/// * `N` - The group size, must match the value used in the original mapping
+///
+/// # Errors
+/// Returns an error if:
+/// - `src` is empty
+/// - `src`'s length is not a multiple of `N+1`
+/// - Delimiter bytes are not 0
+/// - Padding bytes are not 0xFF
+/// - The ending byte contains invalid padding information
+pub fn unmap_buf<const N: usize, B: Buf>(src: &mut B, dst: &mut impl BufMut) -> Result<(), Error> {
+    check_group_size::<N>();
+
+    let len = src.remaining();
+    if len == 0 {
+        return Err(Error::EmptyBytes);
+    }
+    if !len.is_multiple_of(N + 1) {
+        return Err(Error::InvalidLength { len, n: N as u8 });
+    }
+
+    let mut buf = [0u8; N];
+    let mut buf_len = 0;
+    let mut pos = 0;
+    while src.has_remaining() {
+        let val = src.get_u8();
+        if buf_len < N {
+            buf[buf_len] = val;
+            buf_len += 1;
+        } else {
+            if pos + 1 == len {
+                if val == 0 || val as usize > N + 1 {
+                    return Err(Error::InvalidEnding { val });
+                }
+                let padding = val as usize - 1;
+                let data_len = buf_len - padding;
+                for (i, &b) in buf[data_len..buf_len].iter().enumerate() {
+                    if b != 0xff {
+                        return Err(Error::InvalidPadding {
+                            pos: pos - buf_len + data_len + i,
+                            val: b,
+                        });
+                    }
+                }
+                for &b in &buf[..data_len] {
+                    dst.put_u8(!b);
+                }
+            } else {
+                if val != 0 {
+                    return Err(Error::InvalidDelimiter { pos, val });
+                }
+                for &b in &buf[..buf_len] {
+                    dst.put_u8(!b);
+                }
+            }
+            buf_len = 0;
+        }
+        pos += 1;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_map_buf_matches_map() {
+        let bytes_list: &[&[u8]] = &[b"", b"A", b"hello", b"hello world!", b"7268"];
+        for bytes in bytes_list {
+            let mut dst = BytesMut::new();
+            map_buf::<8, _>(bytes, &mut dst);
+
+            // Oversized on purpose: map_into only writes the prefix it needs, so this just
+            // has to be at least encoded_len(longest test vector, 8).
+            let mut expected = [0u8; 64];
+            let expected = crate::core::map_into::<8>(bytes, &mut expected).unwrap();
+            assert_eq!(dst.as_ref(), expected);
+        }
+    }
+
+    #[test]
+    fn test_unmap_buf_round_trip() {
+        let bytes_list: &[&[u8]] = &[b"", b"A", b"hello", b"hello world!", b"7268"];
+        for bytes in bytes_list {
+            let mut mapped = BytesMut::new();
+            map_buf::<8, _>(bytes, &mut mapped);
+
+            let mut unmapped = BytesMut::new();
+            unmap_buf::<8, _>(&mut mapped.freeze(), &mut unmapped).unwrap();
+            assert_eq!(unmapped.as_ref(), *bytes);
+        }
+    }
+
+    #[test]
+    fn test_unmap_buf_invalid_delimiter() {
+        let mut mapped = BytesMut::new();
+        map_buf::<8, _>(b"hello world!", &mut mapped);
+        mapped[8] = 1;
+
+        let mut unmapped = BytesMut::new();
+        assert!(matches!(
+            unmap_buf::<8, _>(&mut mapped.freeze(), &mut unmapped).unwrap_err(),
+            Error::InvalidDelimiter { pos: 8, val: 1 }
+        ));
+    }
+}
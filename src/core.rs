@@ -0,0 +1,468 @@
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+pub(crate) struct Assert<const N: usize>;
+impl<const N: usize> Assert<N> {
+    pub(crate) const ASSERT: () = assert!(0 < N && N < 255, "invalid N!");
+}
+
+/// Panics at compile time (monomorphization) if `N` is not a valid group size (`0 < N < 255`).
+///
+/// Shared by every module that takes a group size `N` as a const generic, so the check lives
+/// in one place instead of being pasted into each one.
+pub(crate) fn check_group_size<const N: usize>() {
+    const { Assert::<N>::ASSERT }
+}
+
+/// Represents possible errors that may occur during byte stream mapping operations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    /// The input byte stream is empty
+    EmptyBytes,
+    /// The input length is invalid (must be a multiple of N+1)
+    InvalidLength { len: usize, n: u8 },
+    /// A delimiter byte is invalid (must be 0)
+    InvalidDelimiter { pos: usize, val: u8 },
+    /// A padding byte is invalid (must be 0xFF)
+    InvalidPadding { pos: usize, val: u8 },
+    /// The ending byte contains invalid padding information
+    InvalidEnding { val: u8 },
+    /// The caller-provided output buffer was too small to hold the result
+    OutputTooSmall { needed: usize, got: usize },
+    /// A byte-stuffed field ended before its terminator was reached
+    UnterminatedField { pos: usize },
+}
+
+/// Returns the exact number of bytes `map_into::<N>` writes for an input of `input_len` bytes.
+///
+/// Callers that want to map into a stack buffer (e.g. a `[u8; K]`) can use this to size it.
+pub const fn encoded_len(input_len: usize, n: u8) -> usize {
+    debug_assert!(n > 0, "group size must be nonzero");
+    let n = n as usize;
+    (if input_len > 1 { input_len } else { 1 }).div_ceil(n) * (n + 1)
+}
+
+/// Returns an upper bound on the number of bytes `unmap_into::<N>` writes for a mapped input of
+/// `input_len` bytes.
+///
+/// The exact output length depends on the padding recorded in the stream and can only be known
+/// after validating it, so this is a safe over-estimate suitable for sizing a buffer up front.
+pub const fn decoded_len_upper_bound(input_len: usize, n: u8) -> usize {
+    debug_assert!(n > 0, "group size must be nonzero");
+    let n = n as usize;
+    input_len / (n + 1) * n
+}
+
+/// Maps the input byte stream into a new byte stream.
+///
+/// This function transforms the input bytes such that for any bytes a < b,
+/// we have map(a) > map(b) in the output stream.
+///
+/// # Type Parameters
+/// * `N` - The group size, must be between 1 and 255
+///
+/// # Arguments
+/// * `bytes` - The input byte slice to be mapped
+///
+/// # Returns
+/// A new vector containing the mapped bytes
+#[cfg(feature = "alloc")]
+pub fn map<const N: usize>(bytes: &[u8]) -> Vec<u8> {
+    let mut out = alloc::vec![0u8; encoded_len(bytes.len(), N as u8)];
+    let len = map_into::<N>(bytes, &mut out).expect("buffer sized via encoded_len").len();
+    out.truncate(len);
+    out
+}
+
+/// Maps the input byte stream into the caller-provided output buffer.
+///
+/// This is the allocation-free counterpart of [`map`]; it writes into `dst` instead of
+/// returning a freshly allocated `Vec`, which makes it usable without the `alloc` feature.
+/// Use [`encoded_len`] to size `dst` ahead of time.
+///
+/// # Type Parameters
+/// * `N` - The group size, must be between 1 and 255
+///
+/// # Arguments
+/// * `bytes` - The input byte slice to be mapped
+/// * `dst` - The output buffer to write into
+///
+/// # Returns
+/// The prefix of `dst` that was written, containing the mapped bytes
+///
+/// # Errors
+/// Returns [`Error::OutputTooSmall`] if `dst` is smaller than `encoded_len(bytes.len(), N)`.
+pub fn map_into<'a, const N: usize>(bytes: &[u8], dst: &'a mut [u8]) -> Result<&'a [u8], Error> {
+    check_group_size::<N>();
+
+    let len = encoded_len(bytes.len(), N as u8);
+    if dst.len() < len {
+        return Err(Error::OutputTooSmall {
+            needed: len,
+            got: dst.len(),
+        });
+    }
+
+    #[cfg(feature = "unsafe-fast-path")]
+    {
+        Ok(fast::map_into::<N>(bytes, &mut dst[..len]))
+    }
+    #[cfg(not(feature = "unsafe-fast-path"))]
+    {
+        Ok(map_into_safe::<N>(bytes, &mut dst[..len]))
+    }
+}
+
+/// Byte-at-a-time fallback for [`map_into`], kept as the default implementation and as the
+/// known-correct reference the `unsafe-fast-path` feature is tested against.
+#[cfg_attr(feature = "unsafe-fast-path", allow(dead_code))]
+fn map_into_safe<'a, const N: usize>(bytes: &[u8], dst: &'a mut [u8]) -> &'a [u8] {
+    let len = dst.len();
+    let mut pos = 0;
+    for (idx, val) in bytes.iter().enumerate() {
+        if idx != 0 && idx % N == 0 {
+            dst[pos] = 0;
+            pos += 1;
+        }
+        dst[pos] = !val;
+        pos += 1;
+    }
+    let m = len - 1 - pos;
+    for b in &mut dst[pos..len - 1] {
+        *b = !0;
+    }
+    dst[len - 1] = (m + 1) as u8;
+    dst
+}
+
+/// Unmaps a previously mapped byte stream back to its original form.
+///
+/// # Type Parameters
+/// * `N` - The group size, must match the value used in the original mapping
+///
+/// # Arguments
+/// * `bytes` - The mapped byte slice to be unmapped
+///
+/// # Returns
+/// * `Ok(Vec<u8>)` - The original byte stream
+/// * `Err(Error)` - If the input is invalid or corrupted
+///
+/// # Errors
+/// Returns an error if:
+/// - The input is empty
+/// - The input length is not a multiple of N+1
+/// - Delimiter bytes are not 0
+/// - Padding bytes are not 0xFF
+/// - The ending byte contains invalid padding information
+#[cfg(feature = "alloc")]
+pub fn unmap<const N: usize>(bytes: &[u8]) -> Result<Vec<u8>, Error> {
+    let mut out = alloc::vec![0u8; decoded_len_upper_bound(bytes.len(), N as u8)];
+    let len = unmap_into::<N>(bytes, &mut out)?.len();
+    out.truncate(len);
+    Ok(out)
+}
+
+/// Unmaps a previously mapped byte stream into the caller-provided output buffer.
+///
+/// This is the allocation-free counterpart of [`unmap`]; it writes into `dst` instead of
+/// returning a freshly allocated `Vec`, which makes it usable without the `alloc` feature.
+/// Use [`decoded_len_upper_bound`] to size `dst` ahead of time.
+///
+/// # Type Parameters
+/// * `N` - The group size, must match the value used in the original mapping
+///
+/// # Arguments
+/// * `bytes` - The mapped byte slice to be unmapped
+/// * `dst` - The output buffer to write into
+///
+/// # Returns
+/// The prefix of `dst` that was written, containing the original byte stream
+///
+/// # Errors
+/// Returns an error if:
+/// - The input is empty
+/// - The input length is not a multiple of N+1
+/// - Delimiter bytes are not 0
+/// - Padding bytes are not 0xFF
+/// - The ending byte contains invalid padding information
+/// - `dst` is smaller than the decoded length
+pub fn unmap_into<'a, const N: usize>(bytes: &[u8], dst: &'a mut [u8]) -> Result<&'a [u8], Error> {
+    check_group_size::<N>();
+
+    if bytes.is_empty() {
+        return Err(Error::EmptyBytes);
+    }
+
+    let chunks = bytes.len() / (N + 1);
+    let mapped_len = chunks * (N + 1);
+    if mapped_len != bytes.len() {
+        return Err(Error::InvalidLength {
+            len: bytes.len(),
+            n: N as u8,
+        });
+    }
+
+    let last = bytes[mapped_len - 1] as usize;
+    let padding = if last == 0 || last > N + 1 {
+        return Err(Error::InvalidEnding { val: last as u8 });
+    } else {
+        last - 1
+    };
+
+    let unmapped_len = chunks * N - padding;
+    if dst.len() < unmapped_len {
+        return Err(Error::OutputTooSmall {
+            needed: unmapped_len,
+            got: dst.len(),
+        });
+    }
+
+    #[cfg(feature = "unsafe-fast-path")]
+    {
+        fast::unmap_into::<N>(bytes, &mut dst[..unmapped_len], chunks, unmapped_len)
+    }
+    #[cfg(not(feature = "unsafe-fast-path"))]
+    {
+        unmap_into_safe::<N>(bytes, &mut dst[..unmapped_len], mapped_len, unmapped_len)
+    }
+}
+
+/// Byte-at-a-time fallback for [`unmap_into`], kept as the default implementation and as the
+/// known-correct reference the `unsafe-fast-path` feature is tested against.
+///
+/// Assumes `bytes` already passed the length and ending-byte checks `unmap_into` performs.
+#[cfg_attr(feature = "unsafe-fast-path", allow(dead_code))]
+fn unmap_into_safe<'a, const N: usize>(
+    bytes: &[u8],
+    dst: &'a mut [u8],
+    mapped_len: usize,
+    unmapped_len: usize,
+) -> Result<&'a [u8], Error> {
+    let mut pos = 0;
+    for (idx, &val) in bytes.iter().enumerate() {
+        if (idx + 1) % (N + 1) == 0 {
+            if idx + 1 != mapped_len && val != 0 {
+                return Err(Error::InvalidDelimiter { pos: idx, val });
+            }
+        } else if pos == unmapped_len {
+            if val != 0xff {
+                return Err(Error::InvalidPadding { pos: idx, val });
+            }
+        } else {
+            dst[pos] = !val;
+            pos += 1;
+        }
+    }
+    Ok(&dst[..pos])
+}
+
+/// Pointer-cursor implementations of the `map`/`unmap` hot loops, enabled by the
+/// `unsafe-fast-path` feature.
+///
+/// These avoid the per-byte `idx % N` bounds-checked indexing the default implementations use
+/// (see [`super::map_into_safe`] / [`super::unmap_into_safe`]) by walking `start`/`end` pointers
+/// directly and writing whole `N`-byte groups between delimiters. Both functions assume the
+/// caller already validated lengths and sized `dst` correctly, matching the contract of the
+/// `*_safe` functions they replace; they are cross-checked against those functions by the
+/// `unsafe_fast_path_matches_safe_path` test, and the `map_roundtrip` fuzz target checks that
+/// `unmap` inverts `map` with this feature enabled.
+#[cfg(feature = "unsafe-fast-path")]
+mod fast {
+    use super::Error;
+    use core::marker::PhantomData;
+
+    /// A read-only cursor over a byte slice that advances a raw pointer instead of indexing,
+    /// so the compiler does not need to bounds-check every read.
+    struct Cursor<'a> {
+        cursor: *const u8,
+        end: *const u8,
+        _marker: PhantomData<&'a u8>,
+    }
+
+    impl<'a> Cursor<'a> {
+        #[inline(always)]
+        fn new(bytes: &'a [u8]) -> Self {
+            let cursor = bytes.as_ptr();
+            // SAFETY: `cursor.add(bytes.len())` stays within (one-past-the-end of) `bytes`.
+            let end = unsafe { cursor.add(bytes.len()) };
+            Self {
+                cursor,
+                end,
+                _marker: PhantomData,
+            }
+        }
+
+        #[inline(always)]
+        fn remaining(&self) -> usize {
+            // SAFETY: `cursor` and `end` both derive from the same slice and `cursor <= end`.
+            unsafe { self.end.offset_from(self.cursor) as usize }
+        }
+
+        /// Reads the next byte and advances past it.
+        ///
+        /// # Safety
+        /// The caller must ensure `self.remaining() > 0`.
+        #[inline(always)]
+        unsafe fn next(&mut self) -> u8 {
+            let val = *self.cursor;
+            self.cursor = self.cursor.add(1);
+            val
+        }
+    }
+
+    pub(super) fn map_into<'a, const N: usize>(bytes: &[u8], dst: &'a mut [u8]) -> &'a [u8] {
+        let len = dst.len();
+        let mut input = Cursor::new(bytes);
+        let out_start = dst.as_mut_ptr();
+        // SAFETY: `out` stays within `dst` because every loop below is bounded by `len`, which
+        // `map_into` computed as `encoded_len(bytes.len(), N)` and verified against `dst.len()`.
+        let mut out = out_start;
+        let mut wrote_group = false;
+
+        while input.remaining() >= N {
+            if wrote_group {
+                unsafe {
+                    *out = 0;
+                    out = out.add(1);
+                }
+            }
+            for _ in 0..N {
+                unsafe {
+                    *out = !input.next();
+                    out = out.add(1);
+                }
+            }
+            wrote_group = true;
+        }
+
+        let tail = input.remaining();
+        if tail > 0 || !wrote_group {
+            if wrote_group {
+                unsafe {
+                    *out = 0;
+                    out = out.add(1);
+                }
+            }
+            for _ in 0..tail {
+                unsafe {
+                    *out = !input.next();
+                    out = out.add(1);
+                }
+            }
+        }
+
+        // SAFETY: `out` is within `[out_start, out_start + len)` by construction above.
+        let written = unsafe { out.offset_from(out_start) as usize };
+        let padding = len - 1 - written;
+        // SAFETY: `[out, out + padding)` is within `[out_start, out_start + len)` since
+        // `padding == len - 1 - written`.
+        unsafe {
+            out.write_bytes(!0, padding);
+            out = out.add(padding);
+            *out = (padding + 1) as u8;
+        }
+        // SAFETY: exactly `len` bytes of `dst` were initialized above.
+        unsafe { core::slice::from_raw_parts(out_start, len) }
+    }
+
+    pub(super) fn unmap_into<'a, const N: usize>(
+        bytes: &[u8],
+        dst: &'a mut [u8],
+        chunks: usize,
+        unmapped_len: usize,
+    ) -> Result<&'a [u8], Error> {
+        let mut in_ptr = bytes.as_ptr();
+        let out_start = dst.as_mut_ptr();
+        let mut out = out_start;
+
+        for chunk in 0..chunks {
+            let is_last = chunk + 1 == chunks;
+            let data_in_chunk = if is_last { unmapped_len - chunk * N } else { N };
+
+            for i in 0..N {
+                // SAFETY: `chunks * (N + 1) == bytes.len()`, verified by the caller, so every
+                // `in_ptr` read below stays within `bytes`.
+                let val = unsafe { *in_ptr };
+                if i < data_in_chunk {
+                    // SAFETY: at most `unmapped_len <= dst.len()` real bytes are written,
+                    // bounded by `data_in_chunk` across all chunks.
+                    unsafe {
+                        *out = !val;
+                        out = out.add(1);
+                    }
+                } else if val != 0xff {
+                    return Err(Error::InvalidPadding {
+                        pos: chunk * (N + 1) + i,
+                        val,
+                    });
+                }
+                in_ptr = unsafe { in_ptr.add(1) };
+            }
+
+            // SAFETY: the delimiter/count byte is the `N`-th byte of this chunk, still within
+            // `bytes` per the same bound as the loop above.
+            let marker = unsafe { *in_ptr };
+            in_ptr = unsafe { in_ptr.add(1) };
+            if !is_last && marker != 0 {
+                return Err(Error::InvalidDelimiter {
+                    pos: chunk * (N + 1) + N,
+                    val: marker,
+                });
+            }
+        }
+
+        // SAFETY: exactly `unmapped_len` bytes of `dst` were initialized above.
+        Ok(unsafe { core::slice::from_raw_parts(out_start, unmapped_len) })
+    }
+}
+
+#[cfg(all(test, feature = "unsafe-fast-path", feature = "alloc"))]
+mod fast_path_tests {
+    use super::*;
+
+    fn check<const N: usize>() {
+        for len in 0..(4 * N + 5) {
+            let bytes: alloc::vec::Vec<u8> = (0..len)
+                .map(|i| (i as u8).wrapping_mul(37).wrapping_add(11))
+                .collect();
+
+            let mut safe_mapped = alloc::vec![0u8; encoded_len(bytes.len(), N as u8)];
+            let safe_mapped = map_into_safe::<N>(&bytes, &mut safe_mapped).to_vec();
+
+            let mut fast_mapped = alloc::vec![0u8; encoded_len(bytes.len(), N as u8)];
+            let fast_mapped = fast::map_into::<N>(&bytes, &mut fast_mapped).to_vec();
+
+            assert_eq!(safe_mapped, fast_mapped, "map mismatch for N={N}, len={len}");
+
+            let chunks = fast_mapped.len() / (N + 1);
+
+            let mut safe_round = alloc::vec![0u8; bytes.len()];
+            let safe_round = unmap_into_safe::<N>(
+                &fast_mapped,
+                &mut safe_round,
+                fast_mapped.len(),
+                bytes.len(),
+            )
+            .unwrap()
+            .to_vec();
+
+            let mut fast_round = alloc::vec![0u8; bytes.len()];
+            let fast_round = fast::unmap_into::<N>(&fast_mapped, &mut fast_round, chunks, bytes.len())
+                .unwrap()
+                .to_vec();
+
+            assert_eq!(safe_round, fast_round, "unmap mismatch for N={N}, len={len}");
+            assert_eq!(safe_round, bytes);
+        }
+    }
+
+    #[test]
+    fn unsafe_fast_path_matches_safe_path() {
+        check::<1>();
+        check::<2>();
+        check::<3>();
+        check::<8>();
+        check::<16>();
+        check::<32>();
+    }
+}
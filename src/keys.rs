@@ -0,0 +1,211 @@
+//! Order-preserving fixed-width encodings for numeric types, built on [`crate::core::map`].
+//!
+//! Each value is first transformed into a big-endian byte sequence whose unsigned,
+//! byte-lexicographic order matches the value's numeric order, then fed through the existing
+//! reversing `map`, so the resulting keys sort in descending numeric order. Because the
+//! transform is a fixed-size `to_be_bytes` and the map call writes into a stack array sized by
+//! [`crate::core::encoded_len`], none of this allocates.
+
+use crate::core::{self, Error};
+
+macro_rules! define_unsigned_key {
+    ($map_fn:ident, $unmap_fn:ident, $ty:ty, $width:literal) => {
+        #[doc = concat!(
+            "Maps a `", stringify!($ty), "` into a fixed-size byte key whose reverse-",
+            "lexicographic order matches its numeric order. Big-endian encoding already ",
+            "preserves magnitude order for unsigned integers, so no bias is needed."
+        )]
+        pub fn $map_fn(value: $ty) -> [u8; $width + 1] {
+            let mut out = [0u8; $width + 1];
+            core::map_into::<$width>(&value.to_be_bytes(), &mut out)
+                .expect("fixed-size buffer fits the encoded key");
+            out
+        }
+
+        #[doc = concat!("Inverts [`", stringify!($map_fn), "`], recovering the original value.")]
+        pub fn $unmap_fn(bytes: &[u8]) -> Result<$ty, Error> {
+            let mut be = [0u8; $width];
+            let out = core::unmap_into::<$width>(bytes, &mut be)?;
+            if out.len() != $width {
+                return Err(Error::InvalidLength {
+                    len: bytes.len(),
+                    n: $width,
+                });
+            }
+            let mut arr = [0u8; $width];
+            arr.copy_from_slice(out);
+            Ok(<$ty>::from_be_bytes(arr))
+        }
+    };
+}
+
+macro_rules! define_signed_key {
+    ($map_fn:ident, $unmap_fn:ident, $ty:ty, $uty:ty, $width:literal) => {
+        #[doc = concat!(
+            "Maps a `", stringify!($ty), "` into a fixed-size byte key whose reverse-",
+            "lexicographic order matches its numeric order. The sign bit is flipped first, ",
+            "biasing the value into the unsigned range, so big-endian encoding preserves order ",
+            "across the sign boundary."
+        )]
+        pub fn $map_fn(value: $ty) -> [u8; $width + 1] {
+            let sign_mask: $uty = (1 as $uty).rotate_right(1);
+            let biased = (value as $uty) ^ sign_mask;
+            let mut out = [0u8; $width + 1];
+            core::map_into::<$width>(&biased.to_be_bytes(), &mut out)
+                .expect("fixed-size buffer fits the encoded key");
+            out
+        }
+
+        #[doc = concat!("Inverts [`", stringify!($map_fn), "`], recovering the original value.")]
+        pub fn $unmap_fn(bytes: &[u8]) -> Result<$ty, Error> {
+            let mut be = [0u8; $width];
+            let out = core::unmap_into::<$width>(bytes, &mut be)?;
+            if out.len() != $width {
+                return Err(Error::InvalidLength {
+                    len: bytes.len(),
+                    n: $width,
+                });
+            }
+            let mut arr = [0u8; $width];
+            arr.copy_from_slice(out);
+            let sign_mask: $uty = (1 as $uty).rotate_right(1);
+            Ok((<$uty>::from_be_bytes(arr) ^ sign_mask) as $ty)
+        }
+    };
+}
+
+macro_rules! define_float_key {
+    ($map_fn:ident, $unmap_fn:ident, $ty:ty, $uty:ty, $width:literal) => {
+        #[doc = concat!(
+            "Maps an IEEE-754 `", stringify!($ty), "` into a fixed-size byte key whose reverse-",
+            "lexicographic order matches its numeric order: non-negative values get their sign ",
+            "bit set, negative values have all their bits flipped, so `-inf < ... < +inf` holds ",
+            "in the transformed unsigned space. NaNs sort to both ends rather than one: negative ",
+            "NaNs (sign bit set) map below `-inf`, and positive NaNs map above `+inf`, so a ",
+            "single-sided range check is not enough to exclude them. `-0.0`/`+0.0` map to ",
+            "adjacent-but-distinct keys since they differ in their raw bit pattern."
+        )]
+        pub fn $map_fn(value: $ty) -> [u8; $width + 1] {
+            let sign_mask: $uty = (1 as $uty).rotate_right(1);
+            let bits = value.to_bits();
+            let transformed = if bits & sign_mask == 0 {
+                bits | sign_mask
+            } else {
+                !bits
+            };
+            let mut out = [0u8; $width + 1];
+            core::map_into::<$width>(&transformed.to_be_bytes(), &mut out)
+                .expect("fixed-size buffer fits the encoded key");
+            out
+        }
+
+        #[doc = concat!("Inverts [`", stringify!($map_fn), "`], recovering the original value.")]
+        pub fn $unmap_fn(bytes: &[u8]) -> Result<$ty, Error> {
+            let mut be = [0u8; $width];
+            let out = core::unmap_into::<$width>(bytes, &mut be)?;
+            if out.len() != $width {
+                return Err(Error::InvalidLength {
+                    len: bytes.len(),
+                    n: $width,
+                });
+            }
+            let mut arr = [0u8; $width];
+            arr.copy_from_slice(out);
+            let sign_mask: $uty = (1 as $uty).rotate_right(1);
+            let transformed = <$uty>::from_be_bytes(arr);
+            let bits = if transformed & sign_mask != 0 {
+                transformed & !sign_mask
+            } else {
+                !transformed
+            };
+            Ok(<$ty>::from_bits(bits))
+        }
+    };
+}
+
+define_unsigned_key!(map_u8, unmap_u8, u8, 1);
+define_unsigned_key!(map_u16, unmap_u16, u16, 2);
+define_unsigned_key!(map_u32, unmap_u32, u32, 4);
+define_unsigned_key!(map_u64, unmap_u64, u64, 8);
+define_unsigned_key!(map_u128, unmap_u128, u128, 16);
+
+define_signed_key!(map_i8, unmap_i8, i8, u8, 1);
+define_signed_key!(map_i16, unmap_i16, i16, u16, 2);
+define_signed_key!(map_i32, unmap_i32, i32, u32, 4);
+define_signed_key!(map_i64, unmap_i64, i64, u64, 8);
+define_signed_key!(map_i128, unmap_i128, i128, u128, 16);
+
+define_float_key!(map_f32, unmap_f32, f32, u32, 4);
+define_float_key!(map_f64, unmap_f64, f64, u64, 8);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unsigned_keys_preserve_order() {
+        let values: [u64; 6] = [0, 1, 2, 0xff, 0x100, u64::MAX];
+        for &a in &values {
+            for &b in &values {
+                assert_eq!((a < b), (map_u64(a) > map_u64(b)));
+            }
+        }
+    }
+
+    #[test]
+    fn test_signed_keys_preserve_order() {
+        let values: [i64; 8] = [i64::MIN, -0x100, -2, -1, 0, 1, 2, i64::MAX];
+        for &a in &values {
+            for &b in &values {
+                assert_eq!((a < b), (map_i64(a) > map_i64(b)));
+            }
+        }
+    }
+
+    #[test]
+    fn test_float_keys_preserve_order() {
+        let values: [f64; 8] = [
+            f64::NEG_INFINITY,
+            -1.5,
+            -0.0,
+            0.0,
+            f64::MIN_POSITIVE,
+            1.5,
+            1e300,
+            f64::INFINITY,
+        ];
+        for &a in &values {
+            for &b in &values {
+                if a < b {
+                    assert!(map_f64(a) > map_f64(b));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_float_keys_nan_sorts_to_both_ends() {
+        // NaNs aren't comparable via `<`/`>`, so check their keys directly against the bit
+        // pattern's sign rather than relying on float comparisons.
+        let neg_nan = f64::from_bits(f64::NAN.to_bits() | (1 << 63));
+        let pos_nan = f64::from_bits(f64::NAN.to_bits() & !(1 << 63));
+        assert!(map_f64(neg_nan) > map_f64(f64::NEG_INFINITY));
+        assert!(map_f64(pos_nan) < map_f64(f64::INFINITY));
+    }
+
+    #[test]
+    fn test_keys_round_trip() {
+        assert_eq!(unmap_u64(&map_u64(1234)).unwrap(), 1234);
+        assert_eq!(unmap_i64(&map_i64(-1234)).unwrap(), -1234);
+        assert_eq!(unmap_f64(&map_f64(-1.5)).unwrap(), -1.5);
+    }
+
+    #[test]
+    fn test_unmap_rejects_wrong_width() {
+        let mapped = map_u32(42);
+        assert!(matches!(
+            unmap_u64(&mapped).unwrap_err(),
+            Error::InvalidLength { n: 8, .. }
+        ));
+    }
+}
@@ -1,141 +1,66 @@
-#[doc = include_str!("../README.md")]
-pub mod core {
-    struct Assert<const N: usize>;
-    impl<const N: usize> Assert<N> {
-        const ASSERT: () = assert!(0 < N && N < 255, "invalid N!");
-    }
+#![no_std]
 
-    /// Represents possible errors that may occur during byte stream mapping operations.
-    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-    pub enum Error {
-        /// The input byte stream is empty
-        EmptyBytes,
-        /// The input length is invalid (must be a multiple of N+1)
-        InvalidLength { len: usize, n: u8 },
-        /// A delimiter byte is invalid (must be 0)
-        InvalidDelimiter { pos: usize, val: u8 },
-        /// A padding byte is invalid (must be 0xFF)
-        InvalidPadding { pos: usize, val: u8 },
-        /// The ending byte contains invalid padding information
-        InvalidEnding { val: u8 },
-    }
+#[cfg(feature = "alloc")]
+extern crate alloc;
 
-    /// Maps the input byte stream into a new byte stream.
-    ///
-    /// This function transforms the input bytes such that for any bytes a < b,
-    /// we have map(a) > map(b) in the output stream.
-    ///
-    /// # Type Parameters
-    /// * `N` - The group size, must be between 1 and 255
-    ///
-    /// # Arguments
-    /// * `bytes` - The input byte slice to be mapped
-    ///
-    /// # Returns
-    /// A new vector containing the mapped bytes
-    pub fn map<const N: usize>(bytes: &[u8]) -> Vec<u8> {
-        _ = Assert::<N>::ASSERT;
-
-        let len = (std::cmp::max(bytes.len(), 1) + N - 1) / N * (N + 1);
-        let mut out = Vec::with_capacity(len);
-        for (idx, val) in bytes.iter().enumerate() {
-            if idx != 0 && idx % N == 0 {
-                out.push(0);
-            }
-            out.push(!val);
-        }
-        let m = len - 1 - out.len();
-        out.resize(len - 1, !0);
-        out.push((m + 1) as u8);
-        out
-    }
+#[doc = include_str!("../README.md")]
+pub mod core;
 
-    /// Unmaps a previously mapped byte stream back to its original form.
-    ///
-    /// # Type Parameters
-    /// * `N` - The group size, must match the value used in the original mapping
-    ///
-    /// # Arguments
-    /// * `bytes` - The mapped byte slice to be unmapped
-    ///
-    /// # Returns
-    /// * `Ok(Vec<u8>)` - The original byte stream
-    /// * `Err(Error)` - If the input is invalid or corrupted
-    ///
-    /// # Errors
-    /// Returns an error if:
-    /// - The input is empty
-    /// - The input length is not a multiple of N+1
-    /// - Delimiter bytes are not 0
-    /// - Padding bytes are not 0xFF
-    /// - The ending byte contains invalid padding information
-    pub fn unmap<const N: usize>(bytes: &[u8]) -> std::result::Result<Vec<u8>, Error> {
-        _ = Assert::<N>::ASSERT;
-
-        if bytes.is_empty() {
-            return Err(Error::EmptyBytes);
-        }
+#[cfg(feature = "alloc")]
+pub mod stream;
 
-        let chunks = bytes.len() / (N + 1);
-        let mapped_len = chunks * (N + 1);
-        if mapped_len != bytes.len() {
-            return Err(Error::InvalidLength {
-                len: bytes.len(),
-                n: N as u8,
-            });
-        }
+#[cfg(feature = "alloc")]
+pub mod tuple;
 
-        let last = bytes[mapped_len - 1] as usize;
-        let padding = if last == 0 || last > N + 1 {
-            return Err(Error::InvalidEnding { val: last as u8 });
-        } else {
-            last - 1
-        };
-
-        let unmapped_len = chunks * N - padding;
-        let mut out = Vec::with_capacity(unmapped_len);
-        for (idx, &val) in bytes.iter().enumerate() {
-            if (idx + 1) % (N + 1) == 0 {
-                if idx + 1 != mapped_len && val != 0 {
-                    return Err(Error::InvalidDelimiter { pos: idx, val });
-                }
-            } else {
-                if out.len() == unmapped_len {
-                    if val != 0xff {
-                        return Err(Error::InvalidPadding { pos: idx, val });
-                    }
-                } else {
-                    out.push(!val);
-                }
-            }
-        }
-        Ok(out)
-    }
-}
+#[cfg(feature = "bytes")]
+pub mod buf;
+
+pub mod keys;
 
 pub use core::Error;
 
 /// Maps a byte stream using the default group size (N=8).
 ///
-/// This is a convenience wrapper around core::map with N=8.
+/// This is a convenience wrapper around [`core::map`] with N=8.
 /// See [`core::map`] for detailed documentation.
+#[cfg(feature = "alloc")]
 #[inline(always)]
-pub fn map(bytes: &[u8]) -> Vec<u8> {
+pub fn map(bytes: &[u8]) -> alloc::vec::Vec<u8> {
     core::map::<8>(bytes)
 }
 
 /// Unmaps a byte stream using the default group size (N=8).
 ///
-/// This is a convenience wrapper around core::unmap with N=8.
+/// This is a convenience wrapper around [`core::unmap`] with N=8.
 /// See [`core::unmap`] for detailed documentation.
+#[cfg(feature = "alloc")]
 #[inline(always)]
-pub fn unmap(bytes: &[u8]) -> std::result::Result<Vec<u8>, Error> {
+pub fn unmap(bytes: &[u8]) -> Result<alloc::vec::Vec<u8>, Error> {
     core::unmap::<8>(bytes)
 }
 
-#[cfg(test)]
+/// Maps a byte stream into a caller-provided buffer using the default group size (N=8).
+///
+/// This is a convenience wrapper around [`core::map_into`] with N=8.
+/// See [`core::map_into`] for detailed documentation.
+#[inline(always)]
+pub fn map_into<'a>(bytes: &[u8], dst: &'a mut [u8]) -> Result<&'a [u8], Error> {
+    core::map_into::<8>(bytes, dst)
+}
+
+/// Unmaps a byte stream into a caller-provided buffer using the default group size (N=8).
+///
+/// This is a convenience wrapper around [`core::unmap_into`] with N=8.
+/// See [`core::unmap_into`] for detailed documentation.
+#[inline(always)]
+pub fn unmap_into<'a>(bytes: &[u8], dst: &'a mut [u8]) -> Result<&'a [u8], Error> {
+    core::unmap_into::<8>(bytes, dst)
+}
+
+#[cfg(all(test, feature = "alloc"))]
 mod tests {
     use super::*;
+    use alloc::vec;
 
     #[test]
     fn test_map() {
@@ -147,7 +72,7 @@ mod tests {
         for i in 0..0xff {
             assert!(map(&[]) > map(&vec![i as u8; i + 1]));
             for v in 0..0xff {
-                assert!(map(&vec![v; i + 0]) > map(&vec![v; i + 1]));
+                assert!(map(&vec![v; i]) > map(&vec![v; i + 1]));
                 assert!(map(&vec![v; i + 1]) > map(&vec![v + 1; i + 1]));
                 assert!(map(&vec![v; i + 1]) > map(&vec![v + 1; i + 2]));
                 assert!(map(&vec![v; i + 2]) > map(&vec![v + 1; i + 1]));
@@ -159,7 +84,7 @@ mod tests {
     fn test_unmap() {
         let bytes_list: &[&[u8]] = &[b"", b"A", b"hello", b"hello world!", b"7268"];
         for bytes in bytes_list {
-            assert_eq!(unmap(&map(*bytes)).unwrap(), *bytes);
+            assert_eq!(unmap(&map(bytes)).unwrap(), *bytes);
         }
 
         for i in 0..0xff {
@@ -192,4 +117,34 @@ mod tests {
             Error::InvalidEnding { val: 10 }
         ));
     }
+
+    #[test]
+    fn test_map_into_output_too_small() {
+        let mut dst = [0u8; 4];
+        assert!(matches!(
+            core::map_into::<8>(b"hello", &mut dst),
+            Err(Error::OutputTooSmall { needed: 9, got: 4 })
+        ));
+    }
+
+    #[test]
+    fn test_unmap_into_output_too_small() {
+        let mapped = map(b"hello world!");
+        let mut dst = [0u8; 4];
+        assert!(matches!(
+            core::unmap_into::<8>(&mapped, &mut dst),
+            Err(Error::OutputTooSmall { needed: 12, got: 4 })
+        ));
+    }
+
+    #[test]
+    fn test_sized_buffers_round_trip() {
+        let input = b"hello world!";
+        let mut mapped = vec![0u8; core::encoded_len(input.len(), 8)];
+        let mapped = map_into(input, &mut mapped).unwrap();
+
+        let mut unmapped = vec![0u8; core::decoded_len_upper_bound(mapped.len(), 8)];
+        let unmapped = unmap_into(mapped, &mut unmapped).unwrap();
+        assert_eq!(unmapped, input);
+    }
 }
@@ -0,0 +1,229 @@
+use crate::core::{check_group_size, Error};
+use alloc::vec::Vec;
+
+/// Incrementally maps a byte stream in chunks, without buffering the whole input.
+///
+/// Feed data with repeated calls to [`push`](Encoder::push), then call
+/// [`finish`](Encoder::finish) to emit the trailing padding and the final count byte. This is
+/// the chunked counterpart of [`crate::core::map`], useful when the input arrives piecewise or
+/// is too large to materialize in memory up front.
+#[derive(Debug, Clone)]
+pub struct Encoder<const N: usize> {
+    pos: usize,
+}
+
+impl<const N: usize> Default for Encoder<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> Encoder<N> {
+    /// Creates a new encoder for group size `N`.
+    pub fn new() -> Self {
+        check_group_size::<N>();
+        Self { pos: 0 }
+    }
+
+    /// Feeds the next chunk of plaintext bytes, appending the mapped output to `out`.
+    ///
+    /// Can be called any number of times with arbitrarily sized chunks; group boundaries are
+    /// tracked across calls.
+    pub fn push(&mut self, input: &[u8], out: &mut Vec<u8>) {
+        for &val in input {
+            if self.pos != 0 && self.pos.is_multiple_of(N) {
+                out.push(0);
+            }
+            out.push(!val);
+            self.pos += 1;
+        }
+    }
+
+    /// Finalizes the stream, appending the `0xFF` padding run and the final count byte
+    /// `(m+1)`.
+    pub fn finish(self, out: &mut Vec<u8>) {
+        let rem = self.pos % N;
+        let padding = if self.pos == 0 {
+            N
+        } else if rem == 0 {
+            0
+        } else {
+            N - rem
+        };
+        for _ in 0..padding {
+            out.push(!0);
+        }
+        out.push((padding + 1) as u8);
+    }
+}
+
+/// Incrementally unmaps a previously mapped byte stream in chunks.
+///
+/// Mirrors [`Encoder`]: feed mapped bytes with repeated calls to [`push`](Decoder::push), then
+/// call [`finish`](Decoder::finish) once the stream is exhausted. Because the final group's
+/// trailing byte is a count byte rather than a delimiter, the decoder cannot tell whether a
+/// completed group of data is real plaintext or trailing `0xFF` padding until either another
+/// group begins or `finish` is called — it therefore buffers at most one group of candidate
+/// plaintext bytes at a time.
+#[derive(Debug, Clone)]
+pub struct Decoder<const N: usize> {
+    pos: usize,
+    buf: [u8; N],
+    buf_len: usize,
+    pending_marker: Option<u8>,
+}
+
+impl<const N: usize> Default for Decoder<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> Decoder<N> {
+    /// Creates a new decoder for group size `N`.
+    pub fn new() -> Self {
+        check_group_size::<N>();
+        Self {
+            pos: 0,
+            buf: [0; N],
+            buf_len: 0,
+            pending_marker: None,
+        }
+    }
+
+    /// Feeds the next chunk of mapped bytes, appending any plaintext bytes that can now be
+    /// proven final to `out`.
+    ///
+    /// # Errors
+    /// Returns [`Error::InvalidDelimiter`] if a confirmed delimiter byte is not `0`.
+    pub fn push(&mut self, input: &[u8], out: &mut Vec<u8>) -> Result<(), Error> {
+        for &val in input {
+            if let Some(marker) = self.pending_marker.take() {
+                if marker != 0 {
+                    return Err(Error::InvalidDelimiter {
+                        pos: self.pos - 1,
+                        val: marker,
+                    });
+                }
+                for &b in &self.buf[..self.buf_len] {
+                    out.push(!b);
+                }
+                self.buf_len = 0;
+            }
+
+            if self.buf_len < N {
+                self.buf[self.buf_len] = val;
+                self.buf_len += 1;
+            } else {
+                self.pending_marker = Some(val);
+            }
+            self.pos += 1;
+        }
+        Ok(())
+    }
+
+    /// Finalizes the stream, validating the trailing padding and count byte and appending the
+    /// last group's plaintext bytes to `out`.
+    ///
+    /// # Errors
+    /// Returns an error if the stream is empty, its length is not a multiple of `N+1`, the
+    /// final count byte is out of range, or a byte recorded as padding is not `0xFF`.
+    pub fn finish(self, out: &mut Vec<u8>) -> Result<(), Error> {
+        if self.pos == 0 {
+            return Err(Error::EmptyBytes);
+        }
+        let marker = match self.pending_marker {
+            Some(marker) => marker,
+            None => {
+                return Err(Error::InvalidLength {
+                    len: self.pos,
+                    n: N as u8,
+                });
+            }
+        };
+        if marker == 0 || marker as usize > N + 1 {
+            return Err(Error::InvalidEnding { val: marker });
+        }
+
+        let padding = marker as usize - 1;
+        let data_len = self.buf_len - padding;
+        let group_start = self.pos - self.buf_len - 1;
+        for (i, &b) in self.buf[data_len..self.buf_len].iter().enumerate() {
+            if b != 0xff {
+                return Err(Error::InvalidPadding {
+                    pos: group_start + data_len + i,
+                    val: b,
+                });
+            }
+        }
+        for &b in &self.buf[..data_len] {
+            out.push(!b);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core;
+
+    fn encode_in_chunks<const N: usize>(bytes: &[u8], chunk: usize) -> Vec<u8> {
+        let mut out = Vec::new();
+        let mut encoder = Encoder::<N>::new();
+        for piece in bytes.chunks(chunk.max(1)) {
+            encoder.push(piece, &mut out);
+        }
+        encoder.finish(&mut out);
+        out
+    }
+
+    fn decode_in_chunks<const N: usize>(bytes: &[u8], chunk: usize) -> Result<Vec<u8>, Error> {
+        let mut out = Vec::new();
+        let mut decoder = Decoder::<N>::new();
+        for piece in bytes.chunks(chunk.max(1)) {
+            decoder.push(piece, &mut out)?;
+        }
+        decoder.finish(&mut out)?;
+        Ok(out)
+    }
+
+    #[test]
+    fn test_encoder_matches_map() {
+        let bytes_list: &[&[u8]] = &[b"", b"A", b"hello", b"hello world!", b"7268"];
+        for bytes in bytes_list {
+            for chunk in 1..=bytes.len() + 1 {
+                assert_eq!(encode_in_chunks::<8>(bytes, chunk), core::map::<8>(bytes));
+            }
+        }
+    }
+
+    #[test]
+    fn test_decoder_round_trip() {
+        let bytes_list: &[&[u8]] = &[b"", b"A", b"hello", b"hello world!", b"7268"];
+        for bytes in bytes_list {
+            let mapped = core::map::<8>(bytes);
+            for chunk in 1..=mapped.len() + 1 {
+                assert_eq!(decode_in_chunks::<8>(&mapped, chunk).unwrap(), *bytes);
+            }
+        }
+    }
+
+    #[test]
+    fn test_decoder_invalid_delimiter() {
+        let mut bad = core::map::<8>(b"hello world!");
+        bad[8] = 1;
+        assert!(matches!(
+            decode_in_chunks::<8>(&bad, 3).unwrap_err(),
+            Error::InvalidDelimiter { pos: 8, val: 1 }
+        ));
+    }
+
+    #[test]
+    fn test_decoder_empty_stream() {
+        assert!(matches!(
+            decode_in_chunks::<8>(&[], 1).unwrap_err(),
+            Error::EmptyBytes
+        ));
+    }
+}
@@ -0,0 +1,203 @@
+//! Composite multi-field keys with a per-field sort direction, built on top of
+//! [`crate::core`]'s NOT-based inversion.
+//!
+//! Each field is byte-stuffed so that the fields can be concatenated into one key and split
+//! back apart unambiguously: a literal `0x00` byte in the field is escaped as `0x00 0xFF`, and
+//! the field is terminated by `0x00 0x00`. Because `0x00 0xFF` sorts after the `0x00 0x00`
+//! terminator and before any byte `0x01..=0xFF`, a field sorts before any field it is a prefix
+//! of. Descending fields are stuffed the same way and then have the *entire* stuffed byte
+//! sequence complemented (reusing the same inversion [`crate::core::map`] is built on),
+//! terminator included, so the comparison stays reversed even across a prefix boundary.
+
+use crate::core::Error;
+use alloc::vec::Vec;
+
+fn push_field(field: &[u8], invert: bool, out: &mut Vec<u8>) {
+    let start = out.len();
+    for &raw in field {
+        if raw == 0 {
+            out.push(0);
+            out.push(0xff);
+        } else {
+            out.push(raw);
+        }
+    }
+    out.push(0);
+    out.push(0);
+    if invert {
+        for b in &mut out[start..] {
+            *b = !*b;
+        }
+    }
+}
+
+/// Builds a composite key out of fields that sort independently ascending or descending.
+///
+/// See the [module documentation](self) for the encoding scheme.
+#[derive(Debug, Clone, Default)]
+pub struct TupleEncoder {
+    out: Vec<u8>,
+}
+
+impl TupleEncoder {
+    /// Creates an empty encoder.
+    pub fn new() -> Self {
+        Self { out: Vec::new() }
+    }
+
+    /// Appends a field that should sort ascending, lowest value first.
+    pub fn push_asc(&mut self, field: &[u8]) -> &mut Self {
+        push_field(field, false, &mut self.out);
+        self
+    }
+
+    /// Appends a field that should sort descending, highest value first.
+    pub fn push_desc(&mut self, field: &[u8]) -> &mut Self {
+        push_field(field, true, &mut self.out);
+        self
+    }
+
+    /// Consumes the encoder, returning the composite key.
+    pub fn finish(self) -> Vec<u8> {
+        self.out
+    }
+}
+
+/// Splits a composite key built by [`TupleEncoder`] back into its fields.
+///
+/// The caller must read fields back with the same sequence of directions they were pushed
+/// with, mirroring how `N` must match between [`crate::core::map`] and [`crate::core::unmap`].
+#[derive(Debug, Clone)]
+pub struct TupleDecoder<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> TupleDecoder<'a> {
+    /// Creates a decoder over a composite key produced by [`TupleEncoder`].
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, pos: 0 }
+    }
+
+    /// Reads the next field, un-stuffing it as an ascending field.
+    pub fn next_asc(&mut self) -> Result<Vec<u8>, Error> {
+        self.next_field(false)
+    }
+
+    /// Reads the next field, un-stuffing it as a descending field.
+    pub fn next_desc(&mut self) -> Result<Vec<u8>, Error> {
+        self.next_field(true)
+    }
+
+    /// Returns `true` once every byte of the key has been consumed.
+    pub fn is_empty(&self) -> bool {
+        self.pos >= self.bytes.len()
+    }
+
+    fn next_field(&mut self, invert: bool) -> Result<Vec<u8>, Error> {
+        let mut field = Vec::new();
+        let mut i = self.pos;
+        loop {
+            let Some(&raw) = self.bytes.get(i) else {
+                return Err(Error::UnterminatedField { pos: self.pos });
+            };
+            let byte = if invert { !raw } else { raw };
+            if byte != 0 {
+                field.push(byte);
+                i += 1;
+                continue;
+            }
+            match self.bytes.get(i + 1) {
+                Some(&next_raw) => {
+                    let next = if invert { !next_raw } else { next_raw };
+                    match next {
+                        0 => {
+                            i += 2;
+                            break;
+                        }
+                        0xff => {
+                            field.push(0);
+                            i += 2;
+                        }
+                        val => return Err(Error::InvalidDelimiter { pos: i + 1, val }),
+                    }
+                }
+                None => return Err(Error::UnterminatedField { pos: self.pos }),
+            }
+        }
+        self.pos = i;
+        Ok(field)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let mut encoder = TupleEncoder::new();
+        encoder.push_asc(b"alice");
+        encoder.push_desc(b"bob");
+        encoder.push_asc(b"");
+        encoder.push_asc(&[0, 1, 0xff, 0]);
+        let key = encoder.finish();
+
+        let mut decoder = TupleDecoder::new(&key);
+        assert_eq!(decoder.next_asc().unwrap(), b"alice");
+        assert_eq!(decoder.next_desc().unwrap(), b"bob");
+        assert_eq!(decoder.next_asc().unwrap(), b"");
+        assert_eq!(decoder.next_asc().unwrap(), &[0, 1, 0xff, 0]);
+        assert!(decoder.is_empty());
+    }
+
+    #[test]
+    fn test_ascending_field_order() {
+        let key_of = |field: &[u8]| {
+            let mut encoder = TupleEncoder::new();
+            encoder.push_asc(field);
+            encoder.finish()
+        };
+        assert!(key_of(b"a") < key_of(b"b"));
+        assert!(key_of(b"a") < key_of(b"aa"));
+        assert!(key_of(b"") < key_of(b"a"));
+    }
+
+    #[test]
+    fn test_descending_field_order() {
+        let key_of = |field: &[u8]| {
+            let mut encoder = TupleEncoder::new();
+            encoder.push_desc(field);
+            encoder.finish()
+        };
+        assert!(key_of(b"a") > key_of(b"b"));
+        // Regression: one field is a prefix of the other, so the terminator itself must
+        // carry the reversed sort order, not just the data bytes.
+        assert!(key_of(b"a") > key_of(b"aa"));
+        assert!(key_of(b"bob") > key_of(b"bobby"));
+        assert!(key_of(b"") > key_of(b"x"));
+    }
+
+    #[test]
+    fn test_mixed_direction_composite_order() {
+        let key_of = |a: &[u8], b: &[u8]| {
+            let mut encoder = TupleEncoder::new();
+            encoder.push_asc(a);
+            encoder.push_desc(b);
+            encoder.finish()
+        };
+        // Same ascending prefix: the descending field breaks the tie in reverse.
+        assert!(key_of(b"x", b"a") > key_of(b"x", b"b"));
+        // Different ascending prefix dominates the comparison.
+        assert!(key_of(b"x", b"z") < key_of(b"y", b"a"));
+    }
+
+    #[test]
+    fn test_unterminated_field() {
+        let mut decoder = TupleDecoder::new(b"abc");
+        assert!(matches!(
+            decoder.next_asc().unwrap_err(),
+            Error::UnterminatedField { pos: 0 }
+        ));
+    }
+}